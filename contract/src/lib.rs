@@ -1,18 +1,13 @@
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
     env,
-    json_types::U128,
+    json_types::{U128, U64},
     near_bindgen, require,
     serde::{Deserialize, Serialize},
     store::*,
     AccountId, BorshStorageKey, PanicOnDefault, Promise,
 };
-use near_sdk_contract_tools::{
-    event, 
-    standard::nep297::Event,
-    FungibleToken,
-};
-use near_contract_standards::fungible_token::FungibleToken;
+use near_sdk_contract_tools::{event, standard::nep297::Event};
 
 // -------------------- Events -------------------- //
 
@@ -33,34 +28,100 @@ enum ContractEvent {
         account_id: AccountId,
         amount: U128,
     },
-    OfferAccepted {
+    OfferMatched {
         offer_id: u32,
+        counter_offer_id: u32,
+        amount: U128,
+    },
+    // Added Credit and Withdraw events
+    Credit {
+        account_id: AccountId,
+        amount: U128,
+    },
+    Withdraw {
+        account_id: AccountId,
+        amount: U128,
+    },
+    FeesSwept {
+        amount: U128,
+    },
+    ResolutionProposed {
+        market_id: u32,
+        outcome: bool,
+        resolve_after: U64,
+    },
+    ResolutionDisputed {
         market_id: u32,
         account_id: AccountId,
     },
-    MarketClosed {
+    MarketFinalized {
         market_id: u32,
+        outcome: bool,
     },
-    // Added Credit and Withdraw events 
-    Credit {
+    LiquidityDeposited {
         account_id: AccountId,
         amount: U128,
     },
-    Withdraw {
+    VaultMatched {
+        offer_id: u32,
+        market_id: u32,
+        amount: U128,
+    },
+    LiquidityWithdrawn {
         account_id: AccountId,
         amount: U128,
     },
+    TraderApproved {
+        market_id: u32,
+        account_id: AccountId,
+    },
+    TraderRevoked {
+        market_id: u32,
+        account_id: AccountId,
+    },
 }
 
 // ------------------- Data Structures ------------------- //
 
+// lifecycle of a market's outcome: an open market has no resolution yet; a resolver
+// proposal moves it to Pending until the dispute window elapses; any SharePair
+// participant can escalate a pending proposal to Disputed before then, which only
+// the resolver can resolve via confirm_resolution; Finalized is the terminal state
+// once finalize_market has paid winners out
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+enum MarketStatus {
+    Open,
+    Pending { outcome: bool, resolve_after: U64 },
+    Disputed { outcome: bool },
+    Finalized { outcome: bool },
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 struct Market {
     id: u32,
-    is_open: bool,
+    status: MarketStatus,
     description: String,
     owner: AccountId,
+    // append-only trade history, kept for auditing matched amounts
     shares: Vector<SharePair>,
+    // live per-side position share balances, transferable via ft_transfer,
+    // positive long shares and positive short shares (never both net-zeroed),
+    // both sides of a SharePair are minted its share count, each share redeeming
+    // for one yoctoNEAR if that side wins
+    long_shares: UnorderedMap<AccountId, u128>,
+    short_shares: UnorderedMap<AccountId, u128>,
+    // how much of the liquidity vault is currently deployed as a counterparty in
+    // this market, bounded by the contract's max_vault_exposure_bps; reset to 0
+    // once the market is finalized and the vault's position is settled
+    vault_exposure: u128,
+    // when true, only approved_traders (and the owner/authority) may create_offer
+    // on this market; match_with_vault fills a resting offer whose owner already
+    // passed this check when the offer was placed, so it isn't re-checked there
+    is_permissioned: bool,
+    // account the owner has delegated trader approval to, in addition to themselves
+    authority: Option<AccountId>,
+    approved_traders: LookupSet<AccountId>,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
@@ -70,27 +131,48 @@ pub struct Offer {
     market_id: u32,
     is_long: bool,
     account_id: AccountId,
+    // original size this offer was placed with, net of the protocol fee
     amount: U128,
+    // unmatched size still resting in the book; shrinks as counter-offers fill it
+    remaining: U128,
+    // implied probability, in bps, that this offer's side wins the market
+    odds_bps: u16,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OrderBook {
+    long: Vec<Offer>,
+    short: Vec<Offer>,
+}
+
+// a long offer quoting implied probability `p` (bps) that long wins is compatible
+// with a short offer quoting `q` (bps) that short wins whenever the two cross,
+// i.e. together they cover the full outcome space or more (p + q >= 10_000)
+fn odds_cross(a_bps: u16, b_bps: u16) -> bool {
+    a_bps as u32 + b_bps as u32 >= 10_000
 }
 
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct ViewMarket<'a> {
     id: u32,
-    is_open: bool,
+    status: MarketStatus,
     description: &'a str,
     owner: &'a AccountId,
     shares: u32,
+    is_permissioned: bool,
 }
 
 impl<'a> From<&'a Market> for ViewMarket<'a> {
     fn from(v: &'a Market) -> Self {
         Self {
             id: v.id,
-            is_open: v.is_open,
+            status: v.status,
             description: &v.description,
             owner: &v.owner,
             shares: v.shares.len(),
+            is_permissioned: v.is_permissioned,
         }
     }
 }
@@ -109,6 +191,38 @@ pub struct Contract {
     markets: Vector<Market>,
     credit: LookupMap<AccountId, u128>,
     offers: UnorderedMap<u32, Offer>,
+    // account allowed to sweep accrued protocol fees out of the contract
+    admin: AccountId,
+    // protocol fee taken on offer creation/acceptance, in basis points (1/100th of a percent)
+    fee_bps: u16,
+    // fees collected so far and not yet swept by the admin
+    accrued_fees: u128,
+    // account allowed to propose and confirm market outcomes
+    resolver: AccountId,
+    // how long, in nanoseconds, a proposed outcome can be disputed before it is finalizable
+    dispute_window: U64,
+    // liquidity providers' claims on the vault, denominated in NEAR deposited
+    lp_balances: LookupMap<AccountId, u128>,
+    // sum of all lp_balances, used as the denominator for pro-rata vault withdrawals
+    total_lp_deposits: u128,
+    // NEAR currently pooled in the vault, available to back match_with_vault trades;
+    // grows/shrinks with LP deposits/withdrawals and with the vault's own trading P&L
+    vault_total: u128,
+    // sum of every market's vault_exposure: vault capital currently deployed as a
+    // counterparty and not yet settled back into vault_total by finalize_market
+    vault_exposure_total: u128,
+    // max fraction of vault_total (in bps) that may be deployed into any single market
+    max_vault_exposure_bps: u16,
+    // minimum edge, in bps, the vault requires over the fair complementary odds
+    // before match_with_vault will deploy capital into a resting offer
+    min_vault_edge_bps: u16,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViewShares {
+    long: U128,
+    short: U128,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
@@ -117,30 +231,62 @@ pub enum StorageKey {
     Offers,
     Credit,
     MarketShares(u32),
-}
-
-// TODO: implement fungible token standards to represent shares 
-#[near_bindgen]
-#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
-pub struct Contract {
-    token: FungibleToken,
+    MarketLongShares(u32),
+    MarketShortShares(u32),
+    LpBalances,
+    MarketApprovedTraders(u32),
 }
 
 #[near_bindgen]
 impl Contract {
     #[init]
-    pub fn new() -> Self {
+    pub fn new(
+        admin: AccountId,
+        fee_bps: u16,
+        resolver: AccountId,
+        dispute_window: U64,
+        max_vault_exposure_bps: u16,
+        min_vault_edge_bps: u16,
+    ) -> Self {
+        require!(fee_bps <= 10_000, "fee_bps cannot exceed 10000 (100%)");
+        require!(
+            max_vault_exposure_bps <= 10_000,
+            "max_vault_exposure_bps cannot exceed 10000 (100%)"
+        );
+        require!(
+            min_vault_edge_bps < 10_000,
+            "min_vault_edge_bps must be less than 10000"
+        );
         Self {
             next_offer_id: 0,
             offers: UnorderedMap::new(StorageKey::Offers),
             credit: LookupMap::new(StorageKey::Credit),
             markets: Vector::new(StorageKey::Markets),
+            admin,
+            fee_bps,
+            accrued_fees: 0,
+            resolver,
+            dispute_window,
+            lp_balances: LookupMap::new(StorageKey::LpBalances),
+            total_lp_deposits: 0,
+            vault_total: 0,
+            vault_exposure_total: 0,
+            max_vault_exposure_bps,
+            min_vault_edge_bps,
         }
     }
 
+    // deducts the protocol fee from `amount`, accrues it for the admin to sweep later,
+    // and returns the amount left over to actually stake
+    fn take_fee(&mut self, amount: u128) -> u128 {
+        let fee = amount * self.fee_bps as u128 / 10_000;
+        self.accrued_fees += fee;
+        amount - fee
+    }
+
     // ------------------- Mutative Functions ------------------- //
 
-    pub fn create_market(&mut self, description: String) -> ViewMarket {
+    pub fn create_market(&mut self, description: String, is_permissioned: bool) -> ViewMarket {
         // use length of current markets array as new market id
         let id = self.markets.len();
 
@@ -152,8 +298,14 @@ impl Contract {
             id,
             description,
             owner: owner.clone(),
-            is_open: true,
+            status: MarketStatus::Open,
             shares: Vector::new(StorageKey::MarketShares(id)),
+            long_shares: UnorderedMap::new(StorageKey::MarketLongShares(id)),
+            short_shares: UnorderedMap::new(StorageKey::MarketShortShares(id)),
+            vault_exposure: 0,
+            is_permissioned,
+            authority: None,
+            approved_traders: LookupSet::new(StorageKey::MarketApprovedTraders(id)),
         };
 
         // add new market object into markets array
@@ -172,6 +324,104 @@ impl Contract {
         self.markets.get(id).unwrap().into()
     }
 
+    // the owner and any delegated authority may always trade a permissioned market;
+    // everyone else needs to be on its approved_traders allow-list
+    fn is_approved_trader(market: &Market, account_id: &AccountId) -> bool {
+        !market.is_permissioned
+            || &market.owner == account_id
+            || market.authority.as_ref() == Some(account_id)
+            || market.approved_traders.contains(account_id)
+    }
+
+    // grants `account_id` permission to trade a permissioned market; restricted to
+    // the market owner or its delegated authority
+    pub fn add_trader(&mut self, market_id: u32, account_id: AccountId) {
+        let predecessor = env::predecessor_account_id();
+        let market = self
+            .markets
+            .get_mut(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        require!(
+            market.owner == predecessor || market.authority.as_ref() == Some(&predecessor),
+            "Only the market owner or its delegated authority can approve traders."
+        );
+
+        market.approved_traders.insert(account_id.clone());
+
+        ContractEvent::TraderApproved {
+            market_id,
+            account_id,
+        }
+        .emit();
+    }
+
+    // revokes `account_id`'s permission to trade a permissioned market; restricted
+    // to the market owner or its delegated authority
+    pub fn remove_trader(&mut self, market_id: u32, account_id: AccountId) {
+        let predecessor = env::predecessor_account_id();
+        let market = self
+            .markets
+            .get_mut(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        require!(
+            market.owner == predecessor || market.authority.as_ref() == Some(&predecessor),
+            "Only the market owner or its delegated authority can revoke traders."
+        );
+
+        market.approved_traders.remove(&account_id);
+
+        ContractEvent::TraderRevoked {
+            market_id,
+            account_id,
+        }
+        .emit();
+    }
+
+    // delegates trader-approval authority for a permissioned market to another
+    // account (or clears it); restricted to the market owner
+    pub fn set_market_authority(&mut self, market_id: u32, authority: Option<AccountId>) {
+        let predecessor = env::predecessor_account_id();
+        let market = self
+            .markets
+            .get_mut(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        require!(
+            market.owner == predecessor,
+            "Only the market owner can delegate its authority."
+        );
+
+        market.authority = authority;
+    }
+
+    // mints `amount` of long (or short) position shares for `market` to `account_id`,
+    // scoped by (market_id, is_long) the way per-side balances are stored
+    fn mint_shares(market: &mut Market, is_long: bool, account_id: AccountId, amount: u128) {
+        let balances = if is_long {
+            &mut market.long_shares
+        } else {
+            &mut market.short_shares
+        };
+        *balances.entry(account_id).or_insert(0) += amount;
+    }
+
+    // prices a fill between a long leg and a short leg quoting implied win
+    // probabilities `long_bps`/`short_bps`: a side buys shares at its own quoted
+    // price (so a favored, higher-bps side pays more per share than an underdog),
+    // capped by whichever side's remaining stake runs out first. Returns the
+    // number of 1:1-redeemable shares created and the stake each side contributes
+    // (rounded up, never past that side's remaining); since crossing requires
+    // long_bps + short_bps >= 10_000, the combined stake is always >= the shares
+    // created, and the difference is the matching vig
+    fn price_fill(long_remaining: u128, long_bps: u16, short_remaining: u128, short_bps: u16) -> (u128, u128, u128) {
+        let shares = std::cmp::min(
+            long_remaining * 10_000 / long_bps as u128,
+            short_remaining * 10_000 / short_bps as u128,
+        );
+        let long_stake = (shares * long_bps as u128 + 9_999) / 10_000;
+        let short_stake = (shares * short_bps as u128 + 9_999) / 10_000;
+        (shares, long_stake, short_stake)
+    }
+
     fn credit_account(&mut self, account_id: AccountId, amount: u128) {
         // adds new account entry into credit hashmap, inserts default of 0 if empty, increments with additional amount
         *self.credit.entry(account_id.clone()).or_insert(0) += amount;
@@ -205,153 +455,583 @@ impl Contract {
 
     }
 
-    pub fn close_market(&mut self, market_id: u32, is_long: bool) {
-        // fetch instance of market using market_id
+    // transfers all accrued protocol fees to `to` and resets the counter; restricted
+    // to the stored admin account, mirroring how only the resolver may propose outcomes
+    pub fn sweep_fees(&mut self, to: AccountId) -> Promise {
+        require!(
+            env::predecessor_account_id() == self.admin,
+            "Only the admin can sweep protocol fees."
+        );
+
+        let amount = self.accrued_fees;
+        require!(amount > 0, "There are no accrued fees to sweep.");
+        self.accrued_fees = 0;
+
+        ContractEvent::FeesSwept {
+            amount: amount.into(),
+        }
+        .emit();
+
+        Promise::new(to).transfer(amount)
+    }
+
+    // the resolver proposes an outcome for the market, opening a dispute window
+    // during which any SharePair participant can challenge it before it is finalizable
+    pub fn close_market(&mut self, market_id: u32, outcome: bool) {
+        require!(
+            env::predecessor_account_id() == self.resolver,
+            "Only the resolver can propose a market outcome."
+        );
+
         let market = self
             .markets
             .get_mut(market_id)
             .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        require!(
+            market.status == MarketStatus::Open,
+            "Market already has a proposed or finalized outcome."
+        );
+
+        let resolve_after = env::block_timestamp() + self.dispute_window.0;
+        market.status = MarketStatus::Pending {
+            outcome,
+            resolve_after: resolve_after.into(),
+        };
+
+        ContractEvent::ResolutionProposed {
+            market_id,
+            outcome,
+            resolve_after: resolve_after.into(),
+        }
+        .emit();
+    }
 
-        // ensure that market is still open
-        require!(market.is_open, "Market is already closed.");
+    // escalates a pending proposal into dispute; callable by anyone holding long or
+    // short shares in the market, as long as the dispute window hasn't closed yet
+    pub fn dispute_resolution(&mut self, market_id: u32) {
         let predecessor = env::predecessor_account_id();
+        let market = self
+            .markets
+            .get_mut(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
 
-        // only allow market owner to close the market
+        let outcome = match market.status {
+            MarketStatus::Pending { outcome, resolve_after } => {
+                require!(
+                    env::block_timestamp() < resolve_after.0,
+                    "The dispute window has already closed."
+                );
+                outcome
+            }
+            _ => env::panic_str("Market has no pending resolution to dispute."),
+        };
         require!(
-            market.owner == predecessor,
-            "You are not allowed to close a market you did not create."
+            market.long_shares.get(&predecessor).copied().unwrap_or(0) > 0
+                || market.short_shares.get(&predecessor).copied().unwrap_or(0) > 0,
+            "Only a SharePair participant in this market can dispute its resolution."
+        );
+
+        market.status = MarketStatus::Disputed { outcome };
+
+        ContractEvent::ResolutionDisputed {
+            market_id,
+            account_id: predecessor,
+        }
+        .emit();
+    }
+
+    // lets the resolver confirm (or override) the outcome of a disputed market,
+    // making it immediately finalizable
+    pub fn confirm_resolution(&mut self, market_id: u32, outcome: bool) {
+        require!(
+            env::predecessor_account_id() == self.resolver,
+            "Only the resolver can confirm a disputed resolution."
+        );
+
+        let market = self
+            .markets
+            .get_mut(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        require!(
+            matches!(market.status, MarketStatus::Disputed { .. }),
+            "Market is not under dispute."
         );
 
-        // update state of market to not open
-        market.is_open = false;
+        market.status = MarketStatus::Pending {
+            outcome,
+            resolve_after: env::block_timestamp().into(),
+        };
+
+        ContractEvent::ResolutionProposed {
+            market_id,
+            outcome,
+            resolve_after: env::block_timestamp().into(),
+        }
+        .emit();
+    }
+
+    // runs the credit distribution once a proposed outcome is no longer disputable:
+    // either the dispute window elapsed untouched, or the resolver confirmed an
+    // override after a dispute (which sets resolve_after to the past)
+    pub fn finalize_market(&mut self, market_id: u32) {
+        let market = self
+            .markets
+            .get_mut(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+
+        let outcome = match market.status {
+            MarketStatus::Pending { outcome, resolve_after } => {
+                require!(
+                    env::block_timestamp() >= resolve_after.0,
+                    "The dispute window has not elapsed yet."
+                );
+                outcome
+            }
+            MarketStatus::Disputed { .. } => {
+                env::panic_str("Market is under dispute; the resolver must confirm_resolution first.")
+            }
+            MarketStatus::Finalized { .. } => env::panic_str("Market has already been finalized."),
+            MarketStatus::Open => env::panic_str("Market has no proposed resolution yet."),
+        };
+        market.status = MarketStatus::Finalized { outcome };
 
-        // iterate through shares array in market object access SharePair structs 
-        // return a collection of tuples (account_id, amount)
-        let credits = market
-            .shares
+        // redeem the winning side's shares 1:1 against the credit pool (each share is
+        // worth exactly one yoctoNEAR on a win; odds were already priced in via how
+        // much stake each side paid per share at match time), then burn both sides'
+        // shares now that the market is settled
+        let (winners, losers) = if outcome {
+            (&mut market.long_shares, &mut market.short_shares)
+        } else {
+            (&mut market.short_shares, &mut market.long_shares)
+        };
+        let payouts = winners
             .iter()
-            .map(|s| {
-                (
-                    if is_long {
-                        s.long.clone()
-                    } else {
-                        s.short.clone()
-                    },
-                    s.amount,
-                )
-            })
+            .map(|(account_id, amount)| (account_id.clone(), *amount))
             .collect::<Vec<_>>();
+        winners.clear();
+        losers.clear();
 
-        // emit market closed event     
-        ContractEvent::MarketClosed { market_id }.emit();
+        // the vault's exposure in this market is fully settled either way
+        let vault_exposure = market.vault_exposure;
+        market.vault_exposure = 0;
 
-        // free market resource since it is now closed and out of scope 
+        // emit market finalized event
+        ContractEvent::MarketFinalized { market_id, outcome }.emit();
+
+        // free market resource since it is now settled and out of scope
         drop(market);
 
-        // iterate through credits array and transfer respective shares to creditors 
-        for (creditor, amount) in credits {
-            self.credit_account(creditor, amount.0 * 2);
+        // this exposure is no longer outstanding: it's either about to be credited
+        // back into vault_total below (a vault win) or simply gone (a vault loss)
+        self.vault_exposure_total -= vault_exposure;
+
+        // iterate through payouts array and transfer redeemed shares to creditors;
+        // a win credited to the vault itself flows back into vault_total to be
+        // distributed to LPs pro-rata, instead of into the regular credit balance
+        let vault_id = env::current_account_id();
+        for (creditor, amount) in payouts {
+            let payout = amount;
+            if creditor == vault_id {
+                self.vault_total += payout;
+            } else {
+                self.credit_account(creditor, payout);
+            }
+        }
+    }
+
+    // deposits NEAR into the shared liquidity vault, which match_with_vault can
+    // then deploy as an automatic counterparty for resting offers
+    #[payable]
+    pub fn deposit_liquidity(&mut self) -> U128 {
+        let amount = env::attached_deposit();
+        require!(
+            amount > 0,
+            "You must attach a nonzero amount to deposit liquidity."
+        );
+        let account_id = env::predecessor_account_id();
+
+        *self.lp_balances.entry(account_id.clone()).or_insert(0) += amount;
+        self.total_lp_deposits += amount;
+        self.vault_total += amount;
+
+        ContractEvent::LiquidityDeposited {
+            account_id,
+            amount: amount.into(),
+        }
+        .emit();
+
+        amount.into()
+    }
+
+    // withdraws `amount` of an LP's deposit-denominated claim, paid out pro-rata
+    // against the vault's current value so trading gains/losses are shared fairly
+    pub fn withdraw_liquidity(&mut self, amount: U128) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let balance = self.lp_balances.get(&account_id).copied().unwrap_or(0);
+        require!(
+            balance >= amount.0,
+            "You don't have that much liquidity to withdraw."
+        );
+        require!(self.total_lp_deposits > 0, "There is no liquidity in the vault.");
+        // outstanding match_with_vault positions haven't won or lost yet, so there's
+        // no fair way to mark them for an exiting LP: valuing them at par lets an LP
+        // dodge a loss onto whoever remains, and valuing them at zero forfeits their
+        // share of a win. Block withdrawals entirely until every deployed position,
+        // in every market, has settled back into vault_total.
+        require!(
+            self.vault_exposure_total == 0,
+            "Cannot withdraw while the vault has capital deployed in open markets."
+        );
+
+        let payout = amount.0 * self.vault_total / self.total_lp_deposits;
+
+        self.lp_balances.insert(account_id.clone(), balance - amount.0);
+        self.total_lp_deposits -= amount.0;
+        self.vault_total -= payout;
+
+        ContractEvent::LiquidityWithdrawn {
+            account_id: account_id.clone(),
+            amount: payout.into(),
+        }
+        .emit();
+
+        Promise::new(account_id).transfer(payout)
+    }
+
+    // deploys vault liquidity as the automatic counterparty to a resting offer, up
+    // to whatever's left of the market's max vault exposure cap; fills incrementally
+    // like a regular match and leaves any unmatched remainder resting in the book.
+    // Restricted to the offer's own owner or the admin, since an arbitrary caller
+    // could otherwise force the vault into any resting offer at will; the vault
+    // also only ever prices itself at the fair complementary odds plus
+    // min_vault_edge_bps, so it never deploys capital at less than its required edge
+    pub fn match_with_vault(&mut self, offer_id: u32) -> Offer {
+        let predecessor = env::predecessor_account_id();
+        let mut o = self
+            .offers
+            .remove(&offer_id)
+            .unwrap_or_else(|| env::panic_str("Offer does not exist."));
+        require!(
+            predecessor == o.account_id || predecessor == self.admin,
+            "Only the offer's owner or the admin can trigger a vault match."
+        );
+
+        let market = self
+            .markets
+            .get_mut(o.market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        require!(
+            market.status == MarketStatus::Open,
+            "Market is no longer open for trading."
+        );
+
+        let max_exposure = self.vault_total * self.max_vault_exposure_bps as u128 / 10_000;
+        let available = max_exposure.saturating_sub(market.vault_exposure);
+        let vault_capacity = std::cmp::min(self.vault_total, available);
+
+        // the vault quotes the complement of the offer's odds plus its required
+        // minimum edge, and refuses the match if that quote isn't a valid odds_bps
+        let vault_bps = (10_000 - o.odds_bps) as u32 + self.min_vault_edge_bps as u32;
+        require!(
+            vault_bps < 10_000,
+            "The offer's odds leave no room for the vault's required edge."
+        );
+        let vault_bps = vault_bps as u16;
+        let (long_bps, short_bps) = if o.is_long {
+            (o.odds_bps, vault_bps)
+        } else {
+            (vault_bps, o.odds_bps)
+        };
+        let (long_remaining, short_remaining) = if o.is_long {
+            (o.remaining.0, vault_capacity)
+        } else {
+            (vault_capacity, o.remaining.0)
+        };
+        let (shares, long_stake, short_stake) =
+            Self::price_fill(long_remaining, long_bps, short_remaining, short_bps);
+        require!(
+            shares > 0,
+            "The vault has no capacity left to deploy into this market."
+        );
+
+        let (o_stake, vault_stake) = if o.is_long {
+            (long_stake, short_stake)
+        } else {
+            (short_stake, long_stake)
+        };
+        o.remaining.0 -= o_stake;
+        market.vault_exposure += vault_stake;
+        self.vault_exposure_total += vault_stake;
+        // the vault's required edge is realized immediately as vault_total profit,
+        // on top of (not instead of) its vault_exposure stake settling at finalize_market
+        self.vault_total -= vault_stake;
+        self.vault_total += long_stake + short_stake - shares;
+
+        let vault_id = env::current_account_id();
+        let (long, short) = if o.is_long {
+            (o.account_id.clone(), vault_id)
+        } else {
+            (vault_id, o.account_id.clone())
+        };
+
+        // mint long/short position shares against the matched fill, same as a
+        // regular match, with the vault itself holding the counterparty side
+        Self::mint_shares(market, true, long.clone(), shares);
+        Self::mint_shares(market, false, short.clone(), shares);
+        market.shares.push(SharePair {
+            long,
+            short,
+            amount: shares.into(),
+        });
+
+        ContractEvent::VaultMatched {
+            offer_id,
+            market_id: o.market_id,
+            amount: shares.into(),
+        }
+        .emit();
+
+        if o.remaining.0 > 0 {
+            self.offers.insert(offer_id, o.clone());
         }
+
+        o
+    }
+
+    // scans the resting book for the first offer on `market_id` that is on the
+    // opposite side of `is_long` and whose odds cross ours, excluding our own offers
+    fn find_counter_offer(
+        &self,
+        market_id: u32,
+        account_id: &AccountId,
+        is_long: bool,
+        odds_bps: u16,
+    ) -> Option<u32> {
+        self.offers
+            .iter()
+            .find(|(_, o)| {
+                o.market_id == market_id
+                    && o.is_long != is_long
+                    && &o.account_id != account_id
+                    && odds_cross(
+                        if is_long { odds_bps } else { o.odds_bps },
+                        if is_long { o.odds_bps } else { odds_bps },
+                    )
+            })
+            .map(|(id, _)| *id)
     }
 
     // ------------------- Payable Functions ------------------- //
 
+    // places an offer into the order book for `market_id` at the given implied odds
+    // and immediately matches it against any crossing resting offers, filling
+    // incrementally until either side runs out of size. Each match prices shares at
+    // both sides' own quoted odds (see price_fill), so a favored side pays more per
+    // share than an underdog rather than settling at flat even money. Any unmatched
+    // remainder is left resting in the book for a future offer to match against.
     #[payable]
-    pub fn create_offer(&mut self, market_id: u32, is_long: bool) -> Offer {
-        // set amount as msg.value 
+    pub fn create_offer(&mut self, market_id: u32, is_long: bool, odds_bps: u16) -> Offer {
+        // set amount as msg.value
         let amount = env::attached_deposit();
         require!(
             amount > 0,
             "You must attach a nonzero amount to make an offer."
         );
+        require!(
+            odds_bps > 0 && odds_bps < 10_000,
+            "odds_bps must represent a probability strictly between 0 and 10000."
+        );
 
         let id = self.next_offer_id;
         self.next_offer_id += 1;
         let account_id = env::predecessor_account_id();
 
-        // set account owner as msg.sender 
-        let o = Offer {
+        let market = self
+            .markets
+            .get(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        require!(
+            market.status == MarketStatus::Open,
+            "Market is no longer open for trading."
+        );
+        require!(
+            Self::is_approved_trader(market, &account_id),
+            "You are not an approved trader for this permissioned market."
+        );
+
+        // deduct the protocol fee up front, so the resting offer (and whatever matches
+        // it) is quoted and staked net of fees
+        let amount = self.take_fee(amount);
+
+        let mut o = Offer {
             id,
+            market_id,
             is_long,
             account_id: account_id.clone(),
             amount: amount.into(),
-            market_id,
+            remaining: amount.into(),
+            odds_bps,
         };
 
-        self.offers.insert(id, o.clone());
-
-        // TODO: mint shares to account_id 
-
         ContractEvent::OfferCreated {
             offer_id: id,
             market_id,
             is_long,
-            account_id,
+            account_id: account_id.clone(),
             amount: amount.into(),
         }
         .emit();
 
+        // match against resting counter-offers until we run dry or nothing crosses
+        while o.remaining.0 > 0 {
+            let counter_id = match self.find_counter_offer(market_id, &account_id, is_long, odds_bps) {
+                Some(id) => id,
+                None => break,
+            };
+
+            // remove the counter-offer from the book while we fill against it, and
+            // reinsert it afterwards if it still has size left resting
+            let mut counter = self.offers.remove(&counter_id).unwrap();
+            let (long_bps, short_bps) = if is_long {
+                (odds_bps, counter.odds_bps)
+            } else {
+                (counter.odds_bps, odds_bps)
+            };
+            let (long_remaining, short_remaining) = if is_long {
+                (o.remaining.0, counter.remaining.0)
+            } else {
+                (counter.remaining.0, o.remaining.0)
+            };
+            let (shares, long_stake, short_stake) =
+                Self::price_fill(long_remaining, long_bps, short_remaining, short_bps);
+            if shares == 0 {
+                // one side's remaining stake is too small to buy a whole share at
+                // this price; leave both resting rather than spin with no progress
+                self.offers.insert(counter_id, counter);
+                break;
+            }
+
+            if is_long {
+                o.remaining.0 -= long_stake;
+                counter.remaining.0 -= short_stake;
+            } else {
+                o.remaining.0 -= short_stake;
+                counter.remaining.0 -= long_stake;
+            }
+            // the gap between what both sides staked and what the shares are worth
+            // is the matching vig, accrued the same way as the flat offer-creation fee
+            self.accrued_fees += long_stake + short_stake - shares;
+
+            let (long, short) = if is_long {
+                (account_id.clone(), counter.account_id.clone())
+            } else {
+                (counter.account_id.clone(), account_id.clone())
+            };
+
+            let market = self
+                .markets
+                .get_mut(market_id)
+                .unwrap_or_else(|| env::panic_str("Market no longer exists!"));
+
+            // mint long/short position shares against the matched fill so the
+            // resulting SharePair is backed by transferable, redeemable share balances
+            Self::mint_shares(market, true, long.clone(), shares);
+            Self::mint_shares(market, false, short.clone(), shares);
+            market.shares.push(SharePair {
+                long,
+                short,
+                amount: shares.into(),
+            });
+
+            ContractEvent::OfferMatched {
+                offer_id: id,
+                counter_offer_id: counter_id,
+                amount: shares.into(),
+            }
+            .emit();
+
+            if counter.remaining.0 > 0 {
+                self.offers.insert(counter_id, counter);
+            }
+        }
+
+        // leftover unmatched stake stays resting in the book
+        if o.remaining.0 > 0 {
+            self.offers.insert(id, o.clone());
+        }
+
         o
     }
 
-    #[payable]
-    pub fn accept_offer(&mut self, offer_id: u32) {
-        let amount = env::attached_deposit();
-        require!(
-            amount > 0,
-            "You must attach a nonzero amount to accept an offer."
-        );
-        let amount: U128 = amount.into();
-
-        // check that offer_id exists 
-        let o = self.offers.remove(&offer_id).unwrap_or_else(|| {
-            env::panic_str("Offer does not exist. Maybe someone already accepted it?")
-        });
+    // ------------------- Share Transfers ------------------- //
 
+    // transfers `amount` of an account's long (or short) position shares in a market
+    // to `receiver_id`. Not a real NEP-141 token (a compliant `ft_transfer` takes
+    // just `(receiver_id, amount, memo)` against a single per-contract token; these
+    // are scoped per (market_id, is_long), so no NEP-141 wallet or indexer will
+    // recognize them) — this only borrows its "exactly one yoctoNEAR" convention to
+    // guard against key-reuse attacks across front-ends
+    #[payable]
+    pub fn ft_transfer(&mut self, market_id: u32, is_long: bool, receiver_id: AccountId, amount: U128) {
         require!(
-            o.amount == amount,
-            "You must attach exactly the same amount as the offer you are accepting."
+            env::attached_deposit() == 1,
+            "Requires attached deposit of exactly 1 yoctoNEAR"
         );
         let predecessor = env::predecessor_account_id();
         require!(
-            predecessor != o.account_id,
-            "You cannot accept your own offer."
+            predecessor != receiver_id,
+            "Sender and receiver should be different"
         );
 
-        // check that market is still open 
         let market = self
             .markets
-            .get_mut(o.market_id)
-            .unwrap_or_else(|| env::panic_str("Market no longer exists!"));
-
-        ContractEvent::OfferAccepted {
-            offer_id,
-            market_id: o.market_id,
-            account_id: predecessor.clone(),
-        }
-        .emit();
-
-        let (long, short) = if o.is_long {
-            (o.account_id, predecessor)
+            .get_mut(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        // a permissioned market's shares may only be transferred to approved traders,
+        // the same gate create_offer enforces when the position is first opened
+        require!(
+            Self::is_approved_trader(market, &receiver_id),
+            "The receiver is not an approved trader for this permissioned market."
+        );
+        let balances = if is_long {
+            &mut market.long_shares
         } else {
-            (predecessor, o.account_id)
+            &mut market.short_shares
         };
 
-        // TODO: mint shares to account_id 
+        let sender_balance = balances.get(&predecessor).copied().unwrap_or(0);
+        require!(sender_balance >= amount.0, "Not enough shares to transfer");
 
-        market.shares.push(SharePair {
-            long,
-            short,
-            amount: o.amount,
-        });
+        balances.insert(predecessor, sender_balance - amount.0);
+        *balances.entry(receiver_id).or_insert(0) += amount.0;
     }
 
     // ------------------- View Functions ------------------- //
 
-    // TODO: add read function to retrieve number of shares per user 
-    pub fun get_shares(&self, offer_id: u32) -> u32 {
-        self.offers.get(offer_id).map()
-    } 
+    pub fn ft_balance_of(&self, market_id: u32, is_long: bool, account_id: AccountId) -> U128 {
+        let market = self
+            .markets
+            .get(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        let balances = if is_long {
+            &market.long_shares
+        } else {
+            &market.short_shares
+        };
+        balances.get(&account_id).copied().unwrap_or(0).into()
+    }
+
+    // returns an account's long and short position share balances for a market
+    pub fn get_shares(&self, market_id: u32, account_id: AccountId) -> ViewShares {
+        let market = self
+            .markets
+            .get(market_id)
+            .unwrap_or_else(|| env::panic_str("Market does not exist!"));
+        ViewShares {
+            long: market.long_shares.get(&account_id).copied().unwrap_or(0).into(),
+            short: market.short_shares.get(&account_id).copied().unwrap_or(0).into(),
+        }
+    }
 
     pub fn get_market(&self, market_id: u32) -> Option<ViewMarket> {
         self.markets.get(market_id).map(|m| m.into())
@@ -373,4 +1053,69 @@ impl Contract {
             })
             .collect()
     }
+
+    // returns the resting order book for a market, grouped by side and
+    // sorted by odds so a front-end can render it as a standard depth chart
+    pub fn get_order_book(&self, market_id: u32) -> OrderBook {
+        let mut long = Vec::new();
+        let mut short = Vec::new();
+        for (_, o) in self.offers.iter() {
+            if o.market_id != market_id {
+                continue;
+            }
+            if o.is_long {
+                long.push(o.clone());
+            } else {
+                short.push(o.clone());
+            }
+        }
+        long.sort_by_key(|o| o.odds_bps);
+        short.sort_by_key(|o| o.odds_bps);
+        OrderBook { long, short }
+    }
+}
+
+// Covers the pure settlement-pricing math (price_fill, odds_cross), which needs no
+// storage or near_sdk VM context to exercise. The vault/fee/dispute accounting this
+// math feeds into is exercised through #[near_bindgen] methods on Contract, which
+// need a near-sdk test VM context (near_sdk::test_utils) and therefore a buildable
+// crate; this snapshot has no Cargo.toml, so that coverage can't be added here.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_fill_charges_the_favorite_more_per_share() {
+        // long is favored at 80% implied, short at 20%; exactly complementary (no vig)
+        let (shares, long_stake, short_stake) = Contract::price_fill(800, 8_000, 200, 2_000);
+        assert_eq!(shares, 1_000);
+        assert_eq!(long_stake, 800);
+        assert_eq!(short_stake, 200);
+    }
+
+    #[test]
+    fn price_fill_is_capped_by_the_smaller_side() {
+        // short only has enough remaining to buy 500 shares at its price; long could
+        // afford more, so the match is capped at short's capacity
+        let (shares, long_stake, short_stake) = Contract::price_fill(10_000, 5_000, 250, 5_000);
+        assert_eq!(shares, 500);
+        assert_eq!(long_stake, 250);
+        assert_eq!(short_stake, 250);
+    }
+
+    #[test]
+    fn price_fill_vig_is_the_overround_above_par() {
+        // both sides quote 60%, crossing with a 20% combined overround
+        let (shares, long_stake, short_stake) = Contract::price_fill(600, 6_000, 600, 6_000);
+        let vig = long_stake + short_stake - shares;
+        assert_eq!(shares, 1_000);
+        assert_eq!(vig, 200);
+    }
+
+    #[test]
+    fn odds_cross_requires_full_coverage_of_the_outcome_space() {
+        assert!(odds_cross(6_000, 4_000));
+        assert!(odds_cross(6_000, 5_000));
+        assert!(!odds_cross(6_000, 3_999));
+    }
 }